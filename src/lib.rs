@@ -12,27 +12,61 @@ use alloc::rc::Rc;
 //use alloc::rc::Weak;
 use alloc::vec::Vec;
 use alloc::collections::VecDeque;
+use alloc::collections::BTreeMap;
 
 pub trait Behavior {
-    fn react(&self, event: Event) -> Effect;  // FIXME: refactor to Result<Effect, Error>
+    fn react(&self, event: Event) -> Result<Effect, Error>;
 }
 
+/// A stable identifier assigned to an `Actor` when `Config` first adopts
+/// it, reproducible across runs because assignment order is deterministic.
+pub type ActorId = usize;
+
 pub struct Actor {
     behavior: RefCell<Box<dyn Behavior>>,
+    supervisor: Option<Rc<Actor>>,
+    exit_hook: RefCell<Option<Box<dyn Behavior>>>,
+    id: RefCell<Option<ActorId>>,
 }
 impl Actor {
     fn new(behavior: Box<dyn Behavior>) -> Rc<Actor> {
         Rc::new(Actor {
             behavior: RefCell::new(behavior),
+            supervisor: None,
+            exit_hook: RefCell::new(None),
+            id: RefCell::new(None),
+        })
+    }
+    fn new_supervised(behavior: Box<dyn Behavior>, supervisor: &Rc<Actor>) -> Rc<Actor> {
+        Rc::new(Actor {
+            behavior: RefCell::new(behavior),
+            supervisor: Some(Rc::clone(supervisor)),
+            exit_hook: RefCell::new(None),
+            id: RefCell::new(None),
         })
     }
 
-    fn dispatch(&self, event: Event) -> Effect {
+    fn dispatch(&self, event: Event) -> Result<Effect, Error> {
         self.behavior.borrow().react(event)
     }
     fn update(&self, behavior: Box<dyn Behavior>) {
         *self.behavior.borrow_mut() = behavior;
     }
+    fn supervisor(&self) -> Option<Rc<Actor>> {
+        self.supervisor.clone()
+    }
+    fn set_exit_hook(&self, behavior: Box<dyn Behavior>) {
+        *self.exit_hook.borrow_mut() = Some(behavior);
+    }
+    fn take_exit_hook(&self) -> Option<Box<dyn Behavior>> {
+        self.exit_hook.borrow_mut().take()
+    }
+    fn id(&self) -> Option<ActorId> {
+        *self.id.borrow()
+    }
+    fn assign_id(&self, id: ActorId) {
+        *self.id.borrow_mut() = Some(id);
+    }
 }
 impl fmt::Debug for Actor {
     fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
@@ -58,14 +92,14 @@ impl Event {
     }
 }
 
-type Error = &'static str;
+pub type Error = &'static str;
 
 #[derive(Debug, PartialEq, Clone)]
 pub enum Message {
     Empty,
     Nat(usize),
     Int(isize),
-    Str(&'static str),
+    Sym(&'static str),
     Addr(Rc<Actor>),
     Maybe(Option<Box<Message>>),
     Pair(Box<Message>, Box<Message>),
@@ -83,6 +117,47 @@ pub enum Message {
         name: &'static str,
         value: Box<Message>,
     },
+    // A pattern sub-term that matches any value. Only meaningful inside a
+    // `Message` used as a pattern, e.g. for `Dataspace` observation.
+    Wildcard,
+    Assert {
+        handle: usize,
+        body: Box<Message>,
+    },
+    Retract {
+        handle: usize,
+    },
+    Observe {
+        pattern: Box<Message>,
+        observer: Rc<Actor>,
+    },
+    Added(usize, Box<Message>),
+    Removed(usize),
+    // Delivered to a supervisor when `victim`'s behavior fails with `reason`.
+    Signal {
+        victim: Rc<Actor>,
+        reason: Error,
+    },
+    // Turn-completion barrier: `cust` is replied to with `Synced` only
+    // after everything already enqueued ahead of this message has been
+    // dispatched.
+    Sync {
+        cust: Rc<Actor>,
+    },
+    Synced,
+}
+
+/// A destination `Effect::send` can deliver a `Message` to.
+///
+/// Implemented for a bare `&Rc<Actor>`, which always delivers, and for
+/// `&cap::Cap`, which routes the message through its caveats first.
+pub trait Sendable {
+    fn route(self, message: Message) -> Option<(Rc<Actor>, Message)>;
+}
+impl Sendable for &Rc<Actor> {
+    fn route(self, message: Message) -> Option<(Rc<Actor>, Message)> {
+        Some((Rc::clone(self), message))
+    }
 }
 
 pub struct Effect {
@@ -90,6 +165,9 @@ pub struct Effect {
     events: VecDeque<Event>,
     state: Option<Box<dyn Behavior>>,
     error: Option<Error>,
+    exit_hook: Option<Box<dyn Behavior>>,
+    restarts: Vec<(Rc<Actor>, Box<dyn Behavior>)>,
+    stopped: Vec<Rc<Actor>>,
 }
 impl Effect {
     pub fn new() -> Self {
@@ -98,6 +176,9 @@ impl Effect {
             events: VecDeque::new(),
             state: None,
             error: None,
+            exit_hook: None,
+            restarts: Vec::new(),
+            stopped: Vec::new(),
         }
     }
 
@@ -106,9 +187,18 @@ impl Effect {
         self.actors.push(Rc::clone(&actor));
         actor
     }
-    pub fn send(&mut self, target: &Rc<Actor>, message: Message) {
-        let event = Event::new(target, message);
-        self.events.push_back(event);
+    /// Like `create`, but faults in the new actor's behavior are routed to
+    /// `supervisor` as a `Message::Signal` instead of being reported as
+    /// unsupervised.
+    pub fn create_supervised(&mut self, behavior: Box<dyn Behavior>, supervisor: &Rc<Actor>) -> Rc<Actor> {
+        let actor = Actor::new_supervised(behavior, supervisor);
+        self.actors.push(Rc::clone(&actor));
+        actor
+    }
+    pub fn send<T: Sendable>(&mut self, target: T, message: Message) {
+        if let Some((actor, message)) = target.route(message) {
+            self.events.push_back(Event::new(&actor, message));
+        }
     }
     pub fn update(&mut self, behavior: Box<dyn Behavior>) {
         self.state = Some(behavior);
@@ -116,18 +206,110 @@ impl Effect {
     pub fn throw(&mut self, reason: Error) {
         self.error = Some(reason);
     }
+    /// Register cleanup work that runs exactly once, when this actor is
+    /// stopped (see `Effect::stop`).
+    pub fn exit_hook(&mut self, behavior: Box<dyn Behavior>) {
+        self.exit_hook = Some(behavior);
+    }
+    /// Supervisor decision: replace `actor`'s behavior with a fresh one.
+    pub fn restart(&mut self, actor: &Rc<Actor>, behavior: Box<dyn Behavior>) {
+        self.restarts.push((Rc::clone(actor), behavior));
+    }
+    /// Supervisor decision: stop `actor`, running its exit hook (if any)
+    /// and replacing its behavior with `idiom::Sink`.
+    pub fn stop(&mut self, actor: &Rc<Actor>) {
+        self.stopped.push(Rc::clone(actor));
+    }
 }
 
 pub struct Config {
     actors: Vec<Rc<Actor>>,
     events: VecDeque<Event>,
+    next_id: ActorId,
+    journal: Option<Vec<(ActorId, Message)>>,
 }
 impl Config {
     pub fn new() -> Self {
         Self {
             actors: Vec::new(),
             events: VecDeque::new(),
+            next_id: 0,
+            journal: None,
+        }
+    }
+
+    /// Start recording every dispatched `(ActorId, Message)` in dispatch
+    /// order, so the run can later be replayed or rewound with `replay`.
+    pub fn enable_journal(&mut self) {
+        self.journal = Some(Vec::new());
+    }
+
+    /// The recorded journal, or an empty slice if journaling is disabled.
+    pub fn journal(&self) -> &[(ActorId, Message)] {
+        match &self.journal {
+            Some(journal) => journal,
+            None => &[],
+        }
+    }
+
+    /// Number of turns recorded in the journal so far.
+    pub fn checkpoint_len(&self) -> usize {
+        self.journal().len()
+    }
+
+    /// Rewind the journal to its first `n` entries, so a debugger can step
+    /// backward by later replaying only that prefix.
+    pub fn truncate_to(&mut self, n: usize) {
+        if let Some(journal) = &mut self.journal {
+            journal.truncate(n);
+        }
+    }
+
+    /// Re-boot from `boot`, then re-deliver the rest of `journal` one
+    /// entry at a time, in recorded order: each entry's target is looked
+    /// up by its `ActorId` and re-sent its recorded `Message`, rather than
+    /// trusting a fresh run to naturally reproduce the same queue.
+    ///
+    /// `boot` accounts for `journal`'s first entry. Driving every
+    /// subsequent turn off the journal itself (instead of whatever the
+    /// replay's own effects happen to enqueue) is what lets a `journal`
+    /// that was edited or `truncate_to`'d for time-travel debugging
+    /// actually change what gets replayed.
+    pub fn replay(boot: Box<dyn Behavior>, journal: &[(ActorId, Message)]) -> Config {
+        let mut config = Config::new();
+        config.enable_journal();
+        config.boot(boot);
+        config.events.clear();  // only the journal drives what happens next
+
+        for (id, message) in journal.iter().skip(1) {
+            let target = config.actor_by_id(*id)
+                .unwrap_or_else(|| panic!("replay: no actor with id {} has been created yet", id));
+            config.events.push_back(Event::new(&target, message.clone()));
+            config.dispatch(1);
+            config.events.clear();
         }
+        config
+    }
+
+    fn next_actor_id(&mut self) -> ActorId {
+        let id = self.next_id;
+        self.next_id += 1;
+        id
+    }
+
+    /// Look up a previously-adopted actor by its stable `ActorId`.
+    fn actor_by_id(&self, id: ActorId) -> Option<Rc<Actor>> {
+        self.actors.iter().find(|actor| actor.id() == Some(id)).cloned()
+    }
+
+    /// Adopt a newly created actor, assigning it a stable id if it does
+    /// not already have one.
+    fn adopt(&mut self, actor: &Rc<Actor>) {
+        if actor.id().is_none() {
+            let id = self.next_actor_id();
+            actor.assign_id(id);
+        }
+        self.actors.push(Rc::clone(actor));
     }
 
     /// Execute bootstrap `behavior` to initialize Config.
@@ -135,7 +317,7 @@ impl Config {
     /// Returns the number of events enqueued.
     pub fn boot(&mut self, behavior: Box<dyn Behavior>) -> usize {
         let actor = Actor::new(behavior);
-        self.actors.push(Rc::clone(&actor));  // FIXME: do we need to retain the bootstrap actor?
+        self.adopt(&actor);  // FIXME: do we need to retain the bootstrap actor?
         let event = Event::new(&actor, Message::Empty);
         self.events.push_back(event);
         self.dispatch(1)  // dispatch bootstrap message
@@ -148,18 +330,18 @@ impl Config {
         while limit > 0 {
             if let Some(event) = self.events.pop_front() {
                 let target = Rc::clone(&event.target);
-                let mut effect = target.dispatch(event);
-                match effect.error {
-                    None => {
-                        if let Some(behavior) = effect.state.take() {
-                            target.update(behavior);
+                if let Some(journal) = &mut self.journal {
+                    let id = target.id().expect("actor must be adopted before dispatch");
+                    journal.push((id, event.message.clone()));
+                }
+                match target.dispatch(event) {
+                    Ok(mut effect) => {
+                        match effect.error.take() {
+                            None => self.apply(&target, effect),
+                            Some(reason) => self.fault(&target, reason),
                         }
-                        self.actors.append(&mut effect.actors);  // FIXME: should convert to Weak references here...
-                        self.events.append(&mut effect.events);
-                    },
-                    Some(reason) => {
-                        println!("FAIL! {}", reason);  // FIXME: should deliver a signal to meta-controller
                     },
+                    Err(reason) => self.fault(&target, reason),
                 }
             } else {
                 break;
@@ -168,18 +350,173 @@ impl Config {
         }
         self.events.len()  // remaining event count
     }
+
+    /// Absorb a successful `Effect` into the running configuration.
+    fn apply(&mut self, target: &Rc<Actor>, mut effect: Effect) {
+        if let Some(behavior) = effect.state.take() {
+            target.update(behavior);
+        }
+        if let Some(hook) = effect.exit_hook.take() {
+            target.set_exit_hook(hook);
+        }
+        for actor in effect.actors.drain(..) {  // FIXME: should convert to Weak references here...
+            self.adopt(&actor);
+        }
+        self.events.append(&mut effect.events);
+        for (actor, behavior) in effect.restarts.drain(..) {
+            actor.update(behavior);
+        }
+        for victim in effect.stopped.drain(..) {
+            self.retire(&victim);
+        }
+    }
+
+    /// Run `victim`'s exit hook (if any) exactly once, then go inert.
+    fn retire(&mut self, victim: &Rc<Actor>) {
+        if let Some(hook) = victim.take_exit_hook() {
+            match hook.react(Event::new(victim, Message::Empty)) {
+                Ok(mut effect) => {
+                    for actor in effect.actors.drain(..) {
+                        self.adopt(&actor);
+                    }
+                    self.events.append(&mut effect.events);
+                },
+                Err(reason) => self.fault(victim, reason),
+            }
+        }
+        victim.update(Box::new(idiom::Sink));
+    }
+
+    /// Route a failed actor's fault to its nearest supervisor, if any.
+    fn fault(&mut self, victim: &Rc<Actor>, reason: Error) {
+        match victim.supervisor() {
+            Some(supervisor) => {
+                let event = Event::new(&supervisor, Message::Signal {
+                    victim: Rc::clone(victim),
+                    reason,
+                });
+                self.events.push_back(event);
+            },
+            None => {
+                println!("FAIL! {} (unsupervised actor {:?})", reason, victim);
+            },
+        }
+    }
 }
 
 pub mod idiom {
     use super::*;
 
+    /// Structurally compare a `pattern` against a `value`, treating any
+    /// `Message::Wildcard` sub-term in `pattern` as matching anything.
+    ///
+    /// `Pair` and `List` recurse term-by-term; every other variant must be
+    /// equal to the corresponding value.
+    pub fn matches(pattern: &Message, value: &Message) -> bool {
+        match pattern {
+            Message::Wildcard => true,
+            Message::Pair(p_head, p_tail) => match value {
+                Message::Pair(v_head, v_tail) => {
+                    matches(p_head, v_head) && matches(p_tail, v_tail)
+                },
+                _ => false,
+            },
+            Message::List(p_items) => match value {
+                Message::List(v_items) => {
+                    p_items.len() == v_items.len()
+                        && p_items.iter().zip(v_items.iter())
+                            .all(|(p, v)| matches(p, v))
+                },
+                _ => false,
+            },
+            _ => pattern == value,
+        }
+    }
+
+    /// A Dataspace actor maintains a set of currently-asserted facts, each
+    /// keyed by the handle its asserter supplied, and notifies registered
+    /// observers as facts are asserted or retracted.
+    ///
+    /// This is modeled on the assert/retract/observe idiom from Syndicate,
+    /// where the assertion-maker owns the handle (so it can retract its own
+    /// fact later) rather than the dataspace minting one, giving ReActor a
+    /// publish/subscribe substrate on top of point-to-point `send`.
+    pub struct Dataspace {
+        facts: BTreeMap<usize, Message>,
+        observers: Vec<(Message, Rc<Actor>)>,
+    }
+    impl Dataspace {
+        #[allow(clippy::new_ret_no_self)]  // factory for a fresh boxed Behavior, not Self
+        pub fn new() -> Box<dyn Behavior> {
+            Box::new(Dataspace {
+                facts: BTreeMap::new(),
+                observers: Vec::new(),
+            })
+        }
+    }
+    impl Behavior for Dataspace {
+        fn react(&self, event: Event) -> Result<Effect, Error> {
+            let mut effect = Effect::new();
+            match event.message {
+                Message::Assert { handle, body } => {
+                    let mut facts = self.facts.clone();
+                    facts.insert(handle, (*body).clone());
+                    for (pattern, observer) in &self.observers {
+                        if matches(pattern, &body) {
+                            effect.send(observer, Message::Added(handle, body.clone()));
+                        }
+                    }
+                    effect.update(Box::new(Dataspace {
+                        facts,
+                        observers: self.observers.clone(),
+                    }));
+                },
+                Message::Retract { handle } => {
+                    let mut facts = self.facts.clone();
+                    if let Some(body) = facts.remove(&handle) {
+                        for (pattern, observer) in &self.observers {
+                            if matches(pattern, &body) {
+                                effect.send(observer, Message::Removed(handle));
+                            }
+                        }
+                    }
+                    effect.update(Box::new(Dataspace {
+                        facts,
+                        observers: self.observers.clone(),
+                    }));
+                },
+                Message::Observe { pattern, observer } => {
+                    for (handle, body) in &self.facts {
+                        if matches(&pattern, body) {
+                            effect.send(&observer, Message::Added(*handle, Box::new(body.clone())));
+                        }
+                    }
+                    let mut observers = self.observers.clone();
+                    observers.push((*pattern, observer));
+                    effect.update(Box::new(Dataspace {
+                        facts: self.facts.clone(),
+                        observers,
+                    }));
+                },
+                _ => return Err("Dataspace: unknown message"),
+            }
+            Ok(effect)
+        }
+    }
+
     /// A Sink actor simply throws away all messages that it receives.
     ///
     /// If we make a Request, but don’t care about the Reply, we use a Sink as the Customer.
     pub struct Sink;
+    impl Sink {
+        #[allow(clippy::new_ret_no_self)]  // factory for a fresh boxed Behavior, not Self
+        pub fn new() -> Box<dyn Behavior> {
+            Box::new(Sink)
+        }
+    }
     impl Behavior for Sink {
-        fn react(&self, _event: Event) -> Effect {
-            Effect::new()
+        fn react(&self, _event: Event) -> Result<Effect, Error> {
+            Ok(Effect::new())
         }
     }
 
@@ -189,11 +526,17 @@ pub mod idiom {
     pub struct Forward {
         pub subject: Rc<Actor>,
     }
+    impl Forward {
+        #[allow(clippy::new_ret_no_self)]  // factory for a fresh boxed Behavior, not Self
+        pub fn new(subject: &Rc<Actor>) -> Box<dyn Behavior> {
+            Box::new(Forward { subject: Rc::clone(subject) })
+        }
+    }
     impl Behavior for Forward {
-        fn react(&self, event: Event) -> Effect {
+        fn react(&self, event: Event) -> Result<Effect, Error> {
             let mut effect = Effect::new();
             effect.send(&self.subject, event.message);
-            effect
+            Ok(effect)
         }
     }
 
@@ -206,14 +549,20 @@ pub mod idiom {
         pub cust: Rc<Actor>,
         pub label: Message,
     }
+    impl Label {
+        #[allow(clippy::new_ret_no_self)]  // factory for a fresh boxed Behavior, not Self
+        pub fn new(cust: &Rc<Actor>, label: Message) -> Box<dyn Behavior> {
+            Box::new(Label { cust: Rc::clone(cust), label })
+        }
+    }
     impl Behavior for Label {
-        fn react(&self, event: Event) -> Effect {
+        fn react(&self, event: Event) -> Result<Effect, Error> {
             let mut effect = Effect::new();
             effect.send(&self.cust, Message::Pair(
                 Box::new(self.label.clone()),
                 Box::new(event.message)
             ));
-            effect
+            Ok(effect)
         }
     }
 
@@ -223,14 +572,330 @@ pub mod idiom {
     pub struct Tag {
         pub cust: Rc<Actor>,
     }
+    impl Tag {
+        #[allow(clippy::new_ret_no_self)]  // factory for a fresh boxed Behavior, not Self
+        pub fn new(cust: &Rc<Actor>) -> Box<dyn Behavior> {
+            Box::new(Tag { cust: Rc::clone(cust) })
+        }
+    }
     impl Behavior for Tag {
-        fn react(&self, event: Event) -> Effect {
+        fn react(&self, event: Event) -> Result<Effect, Error> {
             let mut effect = Effect::new();
             effect.send(&self.cust, Message::Pair(
                 Box::new(Message::Addr(Rc::clone(&event.target))),
                 Box::new(event.message)
             ));
-            effect
+            Ok(effect)
+        }
+    }
+
+    /// A Restarter supervises child actors: on a `Message::Signal` fault
+    /// report, it replaces the victim's behavior with a fresh instance
+    /// produced by `spawn`, implementing a simple one-for-one restart
+    /// strategy on top of `Actor`'s supervision hook.
+    pub struct Restarter {
+        pub spawn: Box<dyn Fn() -> Box<dyn Behavior>>,
+    }
+    impl Restarter {
+        #[allow(clippy::new_ret_no_self)]  // factory for a fresh boxed Behavior, not Self
+        pub fn new(spawn: Box<dyn Fn() -> Box<dyn Behavior>>) -> Box<dyn Behavior> {
+            Box::new(Restarter { spawn })
+        }
+    }
+    impl Behavior for Restarter {
+        fn react(&self, event: Event) -> Result<Effect, Error> {
+            let mut effect = Effect::new();
+            match event.message {
+                Message::Signal { victim, .. } => {
+                    effect.restart(&victim, (self.spawn)());
+                },
+                _ => return Err("Restarter: unknown message"),
+            }
+            Ok(effect)
+        }
+    }
+
+    /// A Sync actor cooperates with the turn-completion barrier protocol.
+    ///
+    /// Ordinary messages are forwarded to `subject`, like `Forward`. A
+    /// `Sync{cust}` request is answered immediately with `Synced`: because
+    /// `Config::dispatch` drains the single `events` queue in FIFO order,
+    /// every message sent to this actor ahead of the `Sync` request has
+    /// already been dispatched by the time this reply goes out.
+    pub struct Sync {
+        pub subject: Rc<Actor>,
+    }
+    impl Sync {
+        #[allow(clippy::new_ret_no_self)]  // factory for a fresh boxed Behavior, not Self
+        pub fn new(subject: &Rc<Actor>) -> Box<dyn Behavior> {
+            Box::new(Sync { subject: Rc::clone(subject) })
+        }
+    }
+    impl Behavior for Sync {
+        fn react(&self, event: Event) -> Result<Effect, Error> {
+            let mut effect = Effect::new();
+            match event.message {
+                Message::Sync { cust } => effect.send(&cust, Message::Synced),
+                message => effect.send(&self.subject, message),
+            }
+            Ok(effect)
+        }
+    }
+
+    /// A join-style counter used by `sync_all` to wait for every fan-out
+    /// target's `Synced` reply before signaling the original customer.
+    struct SyncCounter {
+        cust: Rc<Actor>,
+        remaining: usize,
+    }
+    impl Behavior for SyncCounter {
+        fn react(&self, event: Event) -> Result<Effect, Error> {
+            let mut effect = Effect::new();
+            match event.message {
+                Message::Synced => {
+                    if self.remaining <= 1 {
+                        effect.send(&self.cust, Message::Synced);
+                    } else {
+                        effect.update(Box::new(SyncCounter {
+                            cust: Rc::clone(&self.cust),
+                            remaining: self.remaining - 1,
+                        }));
+                    }
+                },
+                _ => return Err("SyncCounter: expected Synced"),
+            }
+            Ok(effect)
+        }
+    }
+
+    /// Fan a `Sync` barrier out across `targets`, signaling `cust` with
+    /// `Synced` only once every target has reported back.
+    ///
+    /// Echoes the commented `send_to_all` sketch, but joins the replies
+    /// through a counting behavior instead of firing straight to `cust`.
+    pub fn sync_all(effect: &mut Effect, cust: &Rc<Actor>, targets: &[Rc<Actor>]) {
+        if targets.is_empty() {
+            effect.send(cust, Message::Synced);
+            return;
+        }
+        let counter = effect.create(Box::new(SyncCounter {
+            cust: Rc::clone(cust),
+            remaining: targets.len(),
+        }));
+        for target in targets {
+            effect.send(target, Message::Sync { cust: Rc::clone(&counter) });
+        }
+    }
+
+    /// Fork issues a head request and a tail request concurrently, then
+    /// becomes a `Join` waiting on both replies.
+    ///
+    /// Given a `Message::Pair(h_req, t_req)`, `Fork` tags itself as the
+    /// customer for each sub-request (reusing `Tag`, as the commented
+    /// `tag_beh`/`fork_beh` sketch does) so the two replies can be told
+    /// apart when they arrive out of order.
+    pub struct Fork {
+        pub cust: Rc<Actor>,
+        pub head: Rc<Actor>,
+        pub tail: Rc<Actor>,
+    }
+    impl Fork {
+        #[allow(clippy::new_ret_no_self)]  // factory for a fresh boxed Behavior, not Self
+        pub fn new(cust: &Rc<Actor>, head: &Rc<Actor>, tail: &Rc<Actor>) -> Box<dyn Behavior> {
+            Box::new(Fork {
+                cust: Rc::clone(cust),
+                head: Rc::clone(head),
+                tail: Rc::clone(tail),
+            })
+        }
+    }
+    impl Behavior for Fork {
+        fn react(&self, event: Event) -> Result<Effect, Error> {
+            let mut effect = Effect::new();
+            match event.message {
+                Message::Pair(h_req, t_req) => {
+                    let this = Rc::clone(&event.target);
+                    let k_head = effect.create(Tag::new(&this));
+                    let k_tail = effect.create(Tag::new(&this));
+                    effect.send(&self.head, Message::Pair(Box::new(Message::Addr(Rc::clone(&k_head))), h_req));
+                    effect.send(&self.tail, Message::Pair(Box::new(Message::Addr(Rc::clone(&k_tail))), t_req));
+                    effect.update(Box::new(Join {
+                        cust: Rc::clone(&self.cust),
+                        k_head,
+                        k_tail,
+                    }));
+                },
+                _ => return Err("Fork: expected Pair(h_req, t_req)"),
+            }
+            Ok(effect)
+        }
+    }
+
+    /// Join waits for a `Tag`-wrapped reply from each of `k_head` and
+    /// `k_tail`, then sends `cust` a `Pair(head_reply, tail_reply)` in
+    /// canonical head/tail order, regardless of which reply arrived first.
+    pub struct Join {
+        pub cust: Rc<Actor>,
+        pub k_head: Rc<Actor>,
+        pub k_tail: Rc<Actor>,
+    }
+    impl Behavior for Join {
+        fn react(&self, event: Event) -> Result<Effect, Error> {
+            let mut effect = Effect::new();
+            match event.message {
+                Message::Pair(tag, reply) => match *tag {
+                    Message::Addr(actor) if actor == self.k_head => {
+                        effect.update(Box::new(JoinWaitingTail {
+                            cust: Rc::clone(&self.cust),
+                            k_tail: Rc::clone(&self.k_tail),
+                            head_reply: *reply,
+                        }));
+                    },
+                    Message::Addr(actor) if actor == self.k_tail => {
+                        effect.update(Box::new(JoinWaitingHead {
+                            cust: Rc::clone(&self.cust),
+                            k_head: Rc::clone(&self.k_head),
+                            tail_reply: *reply,
+                        }));
+                    },
+                    _ => return Err("Join: reply tagged by unknown actor"),
+                },
+                _ => return Err("Join: expected Pair(Addr(tag), reply)"),
+            }
+            Ok(effect)
+        }
+    }
+
+    // Join's one-slot waiting state once the head reply has arrived.
+    struct JoinWaitingTail {
+        cust: Rc<Actor>,
+        k_tail: Rc<Actor>,
+        head_reply: Message,
+    }
+    impl Behavior for JoinWaitingTail {
+        fn react(&self, event: Event) -> Result<Effect, Error> {
+            let mut effect = Effect::new();
+            match event.message {
+                Message::Pair(tag, reply) => match *tag {
+                    Message::Addr(actor) if actor == self.k_tail => {
+                        effect.send(&self.cust, Message::Pair(Box::new(self.head_reply.clone()), reply));
+                        effect.update(Box::new(Sink));
+                    },
+                    _ => return Err("Join: reply tagged by unknown actor"),
+                },
+                _ => return Err("Join: expected Pair(Addr(tag), reply)"),
+            }
+            Ok(effect)
+        }
+    }
+
+    // Join's one-slot waiting state once the tail reply has arrived.
+    struct JoinWaitingHead {
+        cust: Rc<Actor>,
+        k_head: Rc<Actor>,
+        tail_reply: Message,
+    }
+    impl Behavior for JoinWaitingHead {
+        fn react(&self, event: Event) -> Result<Effect, Error> {
+            let mut effect = Effect::new();
+            match event.message {
+                Message::Pair(tag, reply) => match *tag {
+                    Message::Addr(actor) if actor == self.k_head => {
+                        effect.send(&self.cust, Message::Pair(reply, Box::new(self.tail_reply.clone())));
+                        effect.update(Box::new(Sink));
+                    },
+                    _ => return Err("Join: reply tagged by unknown actor"),
+                },
+                _ => return Err("Join: expected Pair(Addr(tag), reply)"),
+            }
+            Ok(effect)
+        }
+    }
+
+}
+
+pub mod cap {
+    use super::*;
+
+    /// A Caveat inspects (and may rewrite) a `Message` on its way through a
+    /// `Cap`. Returning `None` silently drops the message.
+    pub trait Caveat {
+        fn check(&self, message: &Message) -> Option<Message>;
+    }
+
+    /// A Cap is an attenuated reference to a target actor: a bare address
+    /// plus an ordered list of caveats every outgoing message must pass
+    /// through, borrowed from Syndicate's sturdy-ref/caveat design.
+    ///
+    /// Holding a `Cap` never grants more authority than holding the
+    /// underlying `Rc<Actor>` directly; `attenuate` only ever narrows it.
+    pub struct Cap {
+        target: Rc<Actor>,
+        caveats: Vec<Rc<dyn Caveat>>,
+    }
+    impl Cap {
+        pub fn new(target: &Rc<Actor>) -> Self {
+            Cap {
+                target: Rc::clone(target),
+                caveats: Vec::new(),
+            }
+        }
+
+        /// Produce a strictly-weaker capability with `caveat` appended.
+        ///
+        /// Caveats are never removed, only added, so the result can only
+        /// restrict what the original `Cap` already allowed.
+        pub fn attenuate(&self, caveat: Rc<dyn Caveat>) -> Self {
+            let mut caveats = self.caveats.clone();
+            caveats.push(caveat);
+            Cap {
+                target: Rc::clone(&self.target),
+                caveats,
+            }
+        }
+    }
+    impl Sendable for &Cap {
+        fn route(self, message: Message) -> Option<(Rc<Actor>, Message)> {
+            let mut message = message;
+            for caveat in &self.caveats {
+                match caveat.check(&message) {
+                    Some(rewritten) => message = rewritten,
+                    None => return None,
+                }
+            }
+            Some((Rc::clone(&self.target), message))
+        }
+    }
+
+    /// A caveat that only admits messages structurally matching `pattern`,
+    /// reusing the `idiom::matches` walk (`Message::Wildcard` matches any
+    /// sub-term).
+    pub struct PatternCaveat {
+        pub pattern: Message,
+    }
+    impl Caveat for PatternCaveat {
+        fn check(&self, message: &Message) -> Option<Message> {
+            if idiom::matches(&self.pattern, message) {
+                Some(message.clone())
+            } else {
+                None
+            }
+        }
+    }
+
+    /// A caveat that rewrites every message with an arbitrary function, a
+    /// generalization of the `idiom::Label` decorator.
+    pub struct RewriteCaveat {
+        rewrite: Box<dyn Fn(Message) -> Message>,
+    }
+    impl RewriteCaveat {
+        pub fn new(rewrite: Box<dyn Fn(Message) -> Message>) -> Self {
+            RewriteCaveat { rewrite }
+        }
+    }
+    impl Caveat for RewriteCaveat {
+        fn check(&self, message: &Message) -> Option<Message> {
+            Some((self.rewrite)(message.clone()))
         }
     }
 
@@ -247,7 +912,7 @@ mod tests {
         println!("sink = {:?}", sink);
 
         let event = Event::new(&sink, Message::Empty);
-        let effect = sink.dispatch(event);
+        let effect = sink.dispatch(event).expect("Sink never fails");
 
         assert_eq!(0, effect.actors.len());
         assert_eq!(0, effect.events.len());
@@ -258,11 +923,11 @@ mod tests {
         cust: Rc<Actor>,
     }
     impl Behavior for Once {
-        fn react(&self, event: Event) -> Effect {
+        fn react(&self, event: Event) -> Result<Effect, Error> {
             let mut effect = Effect::new();
             effect.send(&self.cust, event.message);
             effect.update(Box::new(idiom::Sink));
-            effect
+            Ok(effect)
         }
     }
 
@@ -274,7 +939,7 @@ mod tests {
         }));
 
         let event = Event::new(&once, Message::Empty);
-        let effect = once.dispatch(event);
+        let effect = once.dispatch(event).expect("Once never fails");
 
         assert_eq!(0, effect.actors.len());
         assert_eq!(1, effect.events.len());
@@ -287,7 +952,7 @@ mod tests {
         }
 
         let event = Event::new(&once, Message::Empty);
-        let effect = once.dispatch(event);
+        let effect = once.dispatch(event).expect("Sink never fails");
 
         assert_eq!(0, effect.actors.len());
         assert_eq!(0, effect.events.len());
@@ -296,16 +961,16 @@ mod tests {
 
     struct Maker;
     impl Behavior for Maker {
-        fn react(&self, event: Event) -> Effect {
+        fn react(&self, event: Event) -> Result<Effect, Error> {
             let mut effect = Effect::new();
             match event.message {
                 Message::Addr(cust) => {
                     let actor = effect.create(Box::new(idiom::Sink));
                     effect.send(&cust, Message::Addr(Rc::clone(&actor)));
                 },
-                _ => effect.throw("unknown message"),
+                _ => return Err("unknown message"),
             }
-            effect
+            Ok(effect)
         }
     }
 
@@ -314,16 +979,14 @@ mod tests {
         let maker = Actor::new(Box::new(Maker));
 
         let event = Event::new(&maker, Message::Empty);
-        let effect = maker.dispatch(event);
+        let result = maker.dispatch(event);
 
-        assert_eq!(0, effect.actors.len());
-        assert_eq!(0, effect.events.len());
-        println!("Got error = {:?}", effect.error);
-        assert_ne!(None, effect.error);
+        println!("Got error = {:?}", result.as_ref().err());
+        assert!(result.is_err());
 
         let sink = Actor::new(Box::new(idiom::Sink));
         let event = Event::new(&maker, Message::Addr(Rc::clone(&sink)));
-        let effect = maker.dispatch(event);
+        let effect = maker.dispatch(event).expect("Maker should succeed");
 
         assert_eq!(1, effect.actors.len());
         assert_eq!(1, effect.events.len());
@@ -167,6 +167,513 @@ fn tag_decorates_with_self() {
     }
 }
 
+#[test]
+fn dataspace_assert_retract_round_trips_on_caller_handle() {
+    static mut MOCK_MESSAGES: Vec<Message> = Vec::new();
+
+    struct Boot;
+    impl Behavior for Boot {
+        fn react(&self, _event: Event) -> Result<Effect, Error> {
+            let mut effect = Effect::new();
+
+            let cust = effect.create(Box::new(MockCust));
+            let dataspace = effect.create(idiom::Dataspace::new());
+            effect.send(&dataspace, Message::Observe {
+                pattern: Box::new(Message::Wildcard),
+                observer: Rc::clone(&cust),
+            });
+            effect.send(&dataspace, Message::Assert {
+                handle: 99,
+                body: Box::new(Message::Sym("fact")),
+            });
+            effect.send(&dataspace, Message::Retract { handle: 99 });
+
+            Ok(effect)
+        }
+    }
+    struct MockCust;
+    impl Behavior for MockCust {
+        fn react(&self, event: Event) -> Result<Effect, Error> {
+            println!("MockCust: message = {:?}", event.message);
+            unsafe {
+                MOCK_MESSAGES.push(event.message);
+            }
+            Ok(Effect::new())
+        }
+    }
+
+    let mut config = Config::new();
+    let count = config.boot(Box::new(Boot));
+    assert_eq!(3, count);
+
+    // Observe registers, Assert notifies, Retract notifies: 2 replies to cust queued.
+    let count = config.dispatch(3);
+    assert_eq!(2, count);
+
+    let count = config.dispatch(2);
+    assert_eq!(0, count);
+    unsafe {
+        assert_eq!(2, MOCK_MESSAGES.len());
+        assert_eq!(Message::Added(99, Box::new(Message::Sym("fact"))), MOCK_MESSAGES[0]);
+        assert_eq!(Message::Removed(99), MOCK_MESSAGES[1]);
+    }
+}
+
+#[test]
+fn journal_replay_reproduces_truncated_prefix() {
+    struct Boot;
+    impl Behavior for Boot {
+        fn react(&self, _event: Event) -> Result<Effect, Error> {
+            let mut effect = Effect::new();
+
+            let sink = effect.create(Box::new(idiom::Sink));
+            effect.send(&sink, Message::Nat(1));
+            effect.send(&sink, Message::Nat(2));
+
+            Ok(effect)
+        }
+    }
+
+    let mut config = Config::new();
+    config.enable_journal();
+    config.boot(Box::new(Boot));
+    config.dispatch(2);
+    assert_eq!(3, config.checkpoint_len());  // boot + 2 sink deliveries
+
+    config.truncate_to(2);
+    let journal = config.journal().to_vec();
+
+    let replayed = Config::replay(Box::new(Boot), &journal);
+    assert_eq!(journal, replayed.journal().to_vec());
+}
+
+#[test]
+fn cap_pattern_caveat_admits_matching_and_drops_others() {
+    static mut MOCK_MESSAGES: Vec<Message> = Vec::new();
+
+    struct Boot;
+    impl Behavior for Boot {
+        fn react(&self, _event: Event) -> Result<Effect, Error> {
+            let mut effect = Effect::new();
+
+            let cust = effect.create(Box::new(MockCust));
+            let cap = cap::Cap::new(&cust)
+                .attenuate(Rc::new(cap::PatternCaveat { pattern: Message::Sym("allowed") }));
+            effect.send(&cap, Message::Sym("allowed"));
+            effect.send(&cap, Message::Sym("blocked"));
+
+            Ok(effect)
+        }
+    }
+    struct MockCust;
+    impl Behavior for MockCust {
+        fn react(&self, event: Event) -> Result<Effect, Error> {
+            println!("MockCust: message = {:?}", event.message);
+            unsafe {
+                MOCK_MESSAGES.push(event.message);
+            }
+            Ok(Effect::new())
+        }
+    }
+
+    let mut config = Config::new();
+    let count = config.boot(Box::new(Boot));
+    assert_eq!(1, count);  // "blocked" was dropped by the caveat before it ever reached the queue
+
+    let count = config.dispatch(1);
+    assert_eq!(0, count);
+    unsafe {
+        assert_eq!(1, MOCK_MESSAGES.len());
+        assert_eq!(Message::Sym("allowed"), MOCK_MESSAGES[0]);
+    }
+}
+
+#[test]
+fn cap_rewrite_caveat_transforms_message() {
+    static mut MOCK_MESSAGE: Message = Message::Empty;
+
+    struct Boot;
+    impl Behavior for Boot {
+        fn react(&self, _event: Event) -> Result<Effect, Error> {
+            let mut effect = Effect::new();
+
+            let cust = effect.create(Box::new(MockCust));
+            let cap = cap::Cap::new(&cust)
+                .attenuate(Rc::new(cap::RewriteCaveat::new(Box::new(|_msg| Message::Sym("rewritten")))));
+            effect.send(&cap, Message::Sym("original"));
+
+            Ok(effect)
+        }
+    }
+    struct MockCust;
+    impl Behavior for MockCust {
+        fn react(&self, event: Event) -> Result<Effect, Error> {
+            println!("MockCust: message = {:?}", event.message);
+            unsafe {
+                MOCK_MESSAGE = event.message;
+            }
+            Ok(Effect::new())
+        }
+    }
+
+    let mut config = Config::new();
+    let count = config.boot(Box::new(Boot));
+    assert_eq!(1, count);
+
+    let count = config.dispatch(1);
+    assert_eq!(0, count);
+    unsafe {
+        assert_eq!(Message::Sym("rewritten"), MOCK_MESSAGE);
+    }
+}
+
+#[test]
+fn cap_attenuate_only_narrows_never_widens() {
+    static mut MOCK_MESSAGES: Vec<Message> = Vec::new();
+
+    struct Boot;
+    impl Behavior for Boot {
+        fn react(&self, _event: Event) -> Result<Effect, Error> {
+            let mut effect = Effect::new();
+
+            let cust = effect.create(Box::new(MockCust));
+            let wide = cap::Cap::new(&cust)
+                .attenuate(Rc::new(cap::PatternCaveat { pattern: Message::Wildcard }));
+            let narrow = wide.attenuate(Rc::new(cap::PatternCaveat { pattern: Message::Sym("allowed") }));
+
+            effect.send(&wide, Message::Sym("blocked"));     // admitted: wide has no further restriction
+            effect.send(&narrow, Message::Sym("blocked"));   // dropped: narrow's extra caveat rejects it
+            effect.send(&narrow, Message::Sym("allowed"));   // admitted: narrow still allows this one
+
+            Ok(effect)
+        }
+    }
+    struct MockCust;
+    impl Behavior for MockCust {
+        fn react(&self, event: Event) -> Result<Effect, Error> {
+            println!("MockCust: message = {:?}", event.message);
+            unsafe {
+                MOCK_MESSAGES.push(event.message);
+            }
+            Ok(Effect::new())
+        }
+    }
+
+    let mut config = Config::new();
+    let count = config.boot(Box::new(Boot));
+    assert_eq!(2, count);
+
+    let count = config.dispatch(2);
+    assert_eq!(0, count);
+    unsafe {
+        assert_eq!(2, MOCK_MESSAGES.len());
+        assert_eq!(Message::Sym("blocked"), MOCK_MESSAGES[0]);
+        assert_eq!(Message::Sym("allowed"), MOCK_MESSAGES[1]);
+    }
+}
+
+#[test]
+fn supervisor_restarts_child_on_signal() {
+    static mut RESTART_COUNT: usize = 0;
+
+    struct FailOnBoom;
+    impl Behavior for FailOnBoom {
+        fn react(&self, event: Event) -> Result<Effect, Error> {
+            match event.message {
+                Message::Sym("boom") => Err("boom"),
+                _ => Ok(Effect::new()),
+            }
+        }
+    }
+
+    struct Boot;
+    impl Behavior for Boot {
+        fn react(&self, _event: Event) -> Result<Effect, Error> {
+            let mut effect = Effect::new();
+
+            let supervisor = effect.create(idiom::Restarter::new(Box::new(|| {
+                unsafe { RESTART_COUNT += 1; }
+                Box::new(FailOnBoom)
+            })));
+            let child = effect.create_supervised(Box::new(FailOnBoom), &supervisor);
+
+            effect.send(&child, Message::Sym("boom"));
+            effect.send(&child, Message::Sym("boom"));
+
+            Ok(effect)
+        }
+    }
+
+    let mut config = Config::new();
+    let count = config.boot(Box::new(Boot));
+    assert_eq!(2, count);
+
+    // boom, boom, Signal, Signal: both faults are routed to the
+    // supervisor and restart the child once each.
+    let count = config.dispatch(4);
+    assert_eq!(0, count);
+    unsafe {
+        assert_eq!(2, RESTART_COUNT);
+    }
+}
+
+#[test]
+fn stop_runs_exit_hook_exactly_once() {
+    static mut HOOK_RUN_COUNT: usize = 0;
+
+    struct CountingHook;
+    impl Behavior for CountingHook {
+        fn react(&self, _event: Event) -> Result<Effect, Error> {
+            unsafe {
+                HOOK_RUN_COUNT += 1;
+            }
+            Ok(Effect::new())
+        }
+    }
+
+    struct Victim;
+    impl Behavior for Victim {
+        fn react(&self, _event: Event) -> Result<Effect, Error> {
+            let mut effect = Effect::new();
+            effect.exit_hook(Box::new(CountingHook));
+            Ok(effect)
+        }
+    }
+
+    struct Supervisor;
+    impl Behavior for Supervisor {
+        fn react(&self, event: Event) -> Result<Effect, Error> {
+            let mut effect = Effect::new();
+            match event.message {
+                Message::Signal { victim, .. } => effect.stop(&victim),
+                _ => return Err("Supervisor: expected Signal"),
+            }
+            Ok(effect)
+        }
+    }
+
+    struct Boot;
+    impl Behavior for Boot {
+        fn react(&self, _event: Event) -> Result<Effect, Error> {
+            let mut effect = Effect::new();
+
+            let supervisor = effect.create(Box::new(Supervisor));
+            let victim = effect.create_supervised(Box::new(Victim), &supervisor);
+
+            effect.send(&victim, Message::Empty);  // registers the exit hook
+            effect.send(&supervisor, Message::Signal { victim: Rc::clone(&victim), reason: "fail" });
+            effect.send(&supervisor, Message::Signal { victim: Rc::clone(&victim), reason: "fail again" });
+
+            Ok(effect)
+        }
+    }
+
+    let mut config = Config::new();
+    let count = config.boot(Box::new(Boot));
+    assert_eq!(3, count);
+
+    // Both stops are delivered, but the hook only fires on the first.
+    let count = config.dispatch(3);
+    assert_eq!(0, count);
+    unsafe {
+        assert_eq!(1, HOOK_RUN_COUNT);
+    }
+}
+
+#[test]
+fn sync_answers_after_prior_messages_are_forwarded() {
+    static mut MOCK_MESSAGES: Vec<Message> = Vec::new();
+
+    struct Boot;
+    impl Behavior for Boot {
+        fn react(&self, _event: Event) -> Result<Effect, Error> {
+            let mut effect = Effect::new();
+
+            let subject = effect.create(Box::new(MockCust));
+            let sync = effect.create(idiom::Sync::new(&subject));
+            let cust = effect.create(Box::new(MockCust));
+
+            effect.send(&sync, Message::Sym("first"));
+            effect.send(&sync, Message::Sym("second"));
+            effect.send(&sync, Message::Sync { cust: Rc::clone(&cust) });
+
+            Ok(effect)
+        }
+    }
+    struct MockCust;
+    impl Behavior for MockCust {
+        fn react(&self, event: Event) -> Result<Effect, Error> {
+            println!("MockCust: message = {:?}", event.message);
+            unsafe {
+                MOCK_MESSAGES.push(event.message);
+            }
+            Ok(Effect::new())
+        }
+    }
+
+    let mut config = Config::new();
+    let count = config.boot(Box::new(Boot));
+    assert_eq!(3, count);
+
+    // sync forwards "first" and "second" to subject, and answers Sync{cust}
+    // with Synced -- but none of those replies are delivered yet.
+    let count = config.dispatch(3);
+    assert_eq!(3, count);
+
+    let count = config.dispatch(3);
+    assert_eq!(0, count);
+    unsafe {
+        assert_eq!(3, MOCK_MESSAGES.len());
+        assert_eq!(Message::Sym("first"), MOCK_MESSAGES[0]);
+        assert_eq!(Message::Sym("second"), MOCK_MESSAGES[1]);
+        assert_eq!(Message::Synced, MOCK_MESSAGES[2]);
+    }
+}
+
+#[test]
+fn sync_all_fires_only_after_every_target_replies() {
+    static mut MOCK_MESSAGE: Message = Message::Empty;
+    static mut MOCK_CALLS: usize = 0;
+
+    struct Boot;
+    impl Behavior for Boot {
+        fn react(&self, _event: Event) -> Result<Effect, Error> {
+            let mut effect = Effect::new();
+
+            let sink = effect.create(idiom::Sink::new());
+            let a = effect.create(idiom::Sync::new(&sink));
+            let b = effect.create(idiom::Sync::new(&sink));
+            let cust = effect.create(Box::new(MockCust));
+
+            idiom::sync_all(&mut effect, &cust, &[a, b]);
+
+            Ok(effect)
+        }
+    }
+    struct MockCust;
+    impl Behavior for MockCust {
+        fn react(&self, event: Event) -> Result<Effect, Error> {
+            println!("MockCust: message = {:?}", event.message);
+            unsafe {
+                MOCK_CALLS += 1;
+                MOCK_MESSAGE = event.message;
+            }
+            Ok(Effect::new())
+        }
+    }
+
+    let mut config = Config::new();
+    let count = config.boot(Box::new(Boot));
+    assert_eq!(2, count);  // Sync{counter} sent to both `a` and `b`
+
+    // `a` and `b` each forward a Synced reply to the counter.
+    let count = config.dispatch(2);
+    assert_eq!(2, count);
+    unsafe {
+        assert_eq!(0, MOCK_CALLS);
+    }
+
+    // First Synced only decrements the counter; cust hasn't heard yet.
+    let count = config.dispatch(1);
+    assert_eq!(1, count);
+    unsafe {
+        assert_eq!(0, MOCK_CALLS);
+    }
+
+    // Second Synced trips the counter, which finally notifies cust.
+    let count = config.dispatch(1);
+    assert_eq!(1, count);
+    let count = config.dispatch(1);
+    assert_eq!(0, count);
+    unsafe {
+        assert_eq!(1, MOCK_CALLS);
+        assert_eq!(Message::Synced, MOCK_MESSAGE);
+    }
+}
+
+#[test]
+fn fork_join_preserves_canonical_order_despite_late_arrival() {
+    static mut MOCK_MESSAGE: Message = Message::Empty;
+
+    // Echo replies to whoever it was addressed on behalf of -- the
+    // Pair(Addr(cust), req) shape Fork's head/tail requests arrive in.
+    struct Echo {
+        reply: Message,
+    }
+    impl Behavior for Echo {
+        fn react(&self, event: Event) -> Result<Effect, Error> {
+            let mut effect = Effect::new();
+            match event.message {
+                Message::Pair(cust, _req) => match *cust {
+                    Message::Addr(cust) => effect.send(&cust, self.reply.clone()),
+                    _ => return Err("Echo: expected Addr(cust)"),
+                },
+                _ => return Err("Echo: expected Pair(cust, req)"),
+            }
+            Ok(effect)
+        }
+    }
+
+    // An extra hop in front of an Echo, so the head reply takes one more
+    // turn than the tail reply and arrives at Join second.
+    struct Relay {
+        target: Rc<Actor>,
+    }
+    impl Behavior for Relay {
+        fn react(&self, event: Event) -> Result<Effect, Error> {
+            let mut effect = Effect::new();
+            effect.send(&self.target, event.message);
+            Ok(effect)
+        }
+    }
+
+    struct Boot;
+    impl Behavior for Boot {
+        fn react(&self, _event: Event) -> Result<Effect, Error> {
+            let mut effect = Effect::new();
+
+            let head_echo = effect.create(Box::new(Echo { reply: Message::Sym("head-reply") }));
+            let head = effect.create(Box::new(Relay { target: Rc::clone(&head_echo) }));
+            let tail = effect.create(Box::new(Echo { reply: Message::Sym("tail-reply") }));
+            let cust = effect.create(Box::new(MockCust));
+
+            let fork = effect.create(idiom::Fork::new(&cust, &head, &tail));
+            effect.send(&fork, Message::Pair(
+                Box::new(Message::Sym("head-req")),
+                Box::new(Message::Sym("tail-req")),
+            ));
+
+            Ok(effect)
+        }
+    }
+    struct MockCust;
+    impl Behavior for MockCust {
+        fn react(&self, event: Event) -> Result<Effect, Error> {
+            println!("MockCust: message = {:?}", event.message);
+            unsafe {
+                MOCK_MESSAGE = event.message;
+            }
+            Ok(Effect::new())
+        }
+    }
+
+    let mut config = Config::new();
+    let count = config.boot(Box::new(Boot));
+    assert_eq!(1, count);
+
+    // The tail's reply only takes one hop, the head's takes two (through
+    // Relay), so Join sees the tail reply first -- yet the result must
+    // still come out in head/tail order.
+    let count = config.dispatch(20);
+    assert_eq!(0, count);
+    unsafe {
+        assert_eq!(
+            Message::Pair(Box::new(Message::Sym("head-reply")), Box::new(Message::Sym("tail-reply"))),
+            MOCK_MESSAGE
+        );
+    }
+}
+
 /*
 #[test]
 fn can_send_struct_and_num() {